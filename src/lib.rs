@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests;
+mod persistent;
 
-use std::{collections::HashMap, any::Any, thread, sync::{mpsc::{Receiver, SyncSender}, Arc, RwLock}, time::{Instant, Duration}, fmt::Display};
+pub use persistent::{init_persistent, put_persistent, get_persistent, Serializable};
+
+use std::{collections::{HashMap, BTreeMap}, any::Any, thread, sync::{mpsc::{Receiver, SyncSender}, Arc, RwLock}, time::{Instant, Duration}, fmt::Display};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -20,40 +23,152 @@ struct CacheItem {
 	data: Cacheable,
 	expires: Instant,
 	ttl_ms: u64,
+	seq: u64,
+	weight: u64,
 }
 
 enum CacheCommand {
 	Get(String, tokio::sync::mpsc::Sender<Result<Cacheable>>, bool),
-	Put(String, Cacheable, u64),
+	Put(String, Cacheable, u64, u64),
 	Remove(String),
+	GetOrInit(String, tokio::sync::mpsc::Sender<InitReply>),
+	Complete(String, Cacheable, u64),
+	AbandonLead(String),
+	Subscribe(tokio::sync::mpsc::Sender<EvictionEvent>),
+}
+
+/// An entry leaving the cache, and why.
+#[derive(Debug, Clone)]
+pub struct EvictionEvent {
+	pub key: String,
+	pub cause: EvictionCause,
 }
 
-static CACHE_SEND: RwLock<Option<SyncSender<CacheCommand>>> = RwLock::new(None);
+/// Why an entry left the cache, for [`subscribe`] listeners.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionCause {
+	/// Its TTL elapsed, found either on access or by the background GC sweep.
+	Expired,
+	/// A `Put` (or a landed `get_or_init`) overwrote an existing entry,
+	/// including one whose TTL had elapsed but that had not been collected yet.
+	Replaced,
+	/// The cache was at `max_entries`, or over `max_weight`, and this was an
+	/// LRU victim.
+	CapacityEvicted,
+	/// `remove` was called for this key.
+	Removed,
+}
+
+/// Reply to a [`CacheCommand::GetOrInit`]. `Lead` tells the caller it's the
+/// first one in and must compute the value itself (then report back via
+/// `CacheCommand::Complete`); `Hit` and `Done` both carry a ready value, the
+/// difference being whether it came from the cache or from a just-finished
+/// init that this caller was waiting on.
+enum InitReply {
+	Hit(Cacheable),
+	Lead,
+	Done(Cacheable),
+}
+
+/// Guards a [`CacheCommand::GetOrInit`] leader's obligation to report back
+/// via `Complete`. If the leader's future is dropped before that happens
+/// (e.g. wrapped in a `timeout` or a `select!` that picks another branch),
+/// `Drop` tells the cache thread to abandon the lead rather than leaving
+/// every waiter behind it parked forever.
+struct LeadGuard {
+	sender: SyncSender<CacheCommand>,
+	key: Option<String>,
+}
+
+impl LeadGuard {
+	fn new(sender: SyncSender<CacheCommand>, key: String) -> Self {
+		Self { sender, key: Some(key) }
+	}
+
+	/// Call once `Complete` has been sent, so `Drop` doesn't also report an
+	/// abandon for a lead that actually finished.
+	fn disarm(mut self) {
+		self.key = None;
+	}
+}
+
+impl Drop for LeadGuard {
+	fn drop(&mut self) {
+		if let Some(key) = self.key.take() {
+			// A blocking send, not `try_send`: the cache thread drains its
+			// channel in a loop rather than blocking on us, so this can't
+			// deadlock, and a dropped `try_send` here (channel momentarily
+			// full) would strand the key in `in_flight` forever - exactly
+			// the bug this guard exists to prevent.
+			let _ = self.sender.send(CacheCommand::AbandonLead(key));
+		}
+	}
+}
+
+static CACHE_SEND: RwLock<Option<Vec<SyncSender<CacheCommand>>>> = RwLock::new(None);
 
 pub async fn init(command_buffer_size: Option<usize>) -> Result<()> {
-	// Create a comms channel to send from anywhere to the cache thread
-	let (send, recv) = std::sync::mpsc::sync_channel(
-		command_buffer_size.unwrap_or(128)
-	);
+	init_bounded(command_buffer_size, None, false, None, 1, None).await
+}
+
+/// Like [`init`], but additionally caps the cache at `max_entries` live items
+/// and/or `max_weight` total weight (see [`put_weighted`]), and spreads the
+/// cache across `num_shards` independent worker threads (each with its own
+/// `HashMap`, LRU order, and eviction listeners) to remove the single-thread
+/// bottleneck of one `mpsc` queue serializing every `get`/`put`. Keys are
+/// routed to a shard by hash, so the public API is unaffected; GC, capacity,
+/// and weight accounting all become per-shard, so `max_entries`/`max_weight`
+/// bound each shard rather than the cache as a whole.
+///
+/// Once a cap is reached, a `Put` evicts the least-recently-used entry
+/// (tracked by `Get`/`get_refresh` access order) before inserting; plain
+/// `put`/`get_or_init` entries carry a weight of 1, so `max_entries` and
+/// `max_weight` agree unless `put_weighted` is used.
+///
+/// When `admission` is set (and `max_entries` is `Some`), count-based
+/// eviction is gated by a TinyLFU-style Count-Min Sketch: a newcomer only
+/// displaces the LRU victim if it's estimated to be accessed at least as
+/// often. This protects the cache from scan-heavy workloads thrashing out
+/// popular keys. `max_weight` eviction is unconditional LRU, since a
+/// newcomer's weight isn't known to the sketch ahead of `put_weighted`.
+///
+/// `expected_keys` sizes the sketch for the approximate cardinality of the
+/// working set it needs to discriminate between (which, for the scan-heavy
+/// workloads admission exists to protect against, is typically much larger
+/// than `max_entries`); it's only used when `admission` is set, and defaults
+/// to `8 * max_entries` when omitted. The sketch is never narrower than 16
+/// counters per row, whichever way its width is chosen.
+pub async fn init_bounded(command_buffer_size: Option<usize>, max_entries: Option<usize>, admission: bool, max_weight: Option<u64>, num_shards: usize, expected_keys: Option<usize>) -> Result<()> {
+	let num_shards = num_shards.max(1);
 
 	let mut write = CACHE_SEND.write().expect("[MEMCACHE] Cache send is poisoned");
 	if write.is_some() {
 		panic!("[MEMCACHE] Init must only be called once");
 	}
 
-	*write = Some(send);
+	let mut senders = Vec::with_capacity(num_shards);
+	for i in 0..num_shards {
+		// Create a comms channel to send from anywhere to this shard's
+		// cache thread
+		let (send, recv) = std::sync::mpsc::sync_channel(
+			command_buffer_size.unwrap_or(128)
+		);
 
-	// Start the cache thread
-	thread::Builder::new()
-		.name(String::from("memcache"))
-		.spawn(move || run(recv))
-		.map_err(|e| Error::ThreadErr(e.to_string()))?;
+		thread::Builder::new()
+			.name(format!("memcache-{i}"))
+			.spawn(move || run(recv, max_entries, admission, max_weight, expected_keys))
+			.map_err(|e| Error::ThreadErr(e.to_string()))?;
+
+		senders.push(send);
+	}
+
+	*write = Some(senders);
 
 	Ok(())
 }
 
 pub async fn get<T: Any + Send + Sync>(key: String) -> Result<Arc<T>> {
-	let sender = get_sender();
+	let sender = get_sender(&key);
 	let (send, recv) = tokio::sync::mpsc::channel(1);
 	let command = CacheCommand::Get(key.to_string(), send, false);
 	sender.send(command).map_err(|e| Error::ThreadErr(e.to_string()))?;
@@ -62,7 +177,7 @@ pub async fn get<T: Any + Send + Sync>(key: String) -> Result<Arc<T>> {
 }
 
 pub async fn get_refresh<T: Any + Send + Sync>(key: String) -> Result<Arc<T>> {
-	let sender = get_sender();
+	let sender = get_sender(&key);
 	let (send, recv) = tokio::sync::mpsc::channel(1);
 	let command = CacheCommand::Get(key.to_string(), send, true);
 	sender.send(command).map_err(|e| Error::ThreadErr(e.to_string()))?;
@@ -71,18 +186,110 @@ pub async fn get_refresh<T: Any + Send + Sync>(key: String) -> Result<Arc<T>> {
 }
 
 pub async fn put<T: Any + Send + Sync>(key: String, value: T, ttl_ms: u64) -> Result<()> {
-	let sender = get_sender();
-	let command = CacheCommand::Put(key.to_string(), Arc::new(value), ttl_ms);
+	put_weighted(key, value, ttl_ms, 1).await
+}
+
+/// Like [`put`], but records `weight` against a cache initialized with
+/// `max_weight` (see [`init_bounded`]) instead of the default weight of 1.
+/// Since cached values are `Arc<dyn Any>`, the cache thread can't downcast
+/// them to run a generic weigher, so the weight must be supplied here.
+pub async fn put_weighted<T: Any + Send + Sync>(key: String, value: T, ttl_ms: u64, weight: u64) -> Result<()> {
+	put_arc(key, Arc::new(value), ttl_ms, weight).await
+}
+
+/// Like [`put_weighted`], but for a value the caller already holds as an
+/// `Arc` (e.g. `persistent::get_persistent`, which needs to both cache and
+/// return the same value it just deserialized). Crate-internal since
+/// sharing an `Arc` with the caller is only safe for values the cache
+/// itself never mutates, which holds for every value it stores.
+pub(crate) async fn put_arc<T: Any + Send + Sync>(key: String, value: Arc<T>, ttl_ms: u64, weight: u64) -> Result<()> {
+	let sender = get_sender(&key);
+	let command = CacheCommand::Put(key.to_string(), value, ttl_ms, weight);
 	sender.send(command).map_err(|e| Error::ThreadErr(e.to_string()))
 }
 
+/// Gets `key`, computing and caching it via `init` if it's absent or
+/// expired. If multiple callers race on the same missing key, only the
+/// first one runs `init`; the rest wait for that computation to land and
+/// receive the same `Arc`, instead of each recomputing it themselves.
+///
+/// Cancellation-safe: if the leader's future is dropped before `init`
+/// finishes (e.g. raced in a `select!` or wrapped in a `timeout`), the
+/// oldest waiter (if any) is promoted to take over as leader instead of
+/// every waiter for `key` being parked forever.
+pub async fn get_or_init<T, F, Fut>(key: String, ttl_ms: u64, init: F) -> Result<Arc<T>>
+where
+	T: Any + Send + Sync,
+	F: FnOnce() -> Fut,
+	Fut: std::future::Future<Output = T>,
+{
+	let sender = get_sender(&key);
+	let (send, mut recv) = tokio::sync::mpsc::channel(1);
+	let command = CacheCommand::GetOrInit(key.clone(), send);
+	sender.send(command).map_err(|e| Error::ThreadErr(e.to_string()))?;
+
+	let reply = recv.recv().await.ok_or(Error::ThreadDisconnected)?;
+	let value = match reply {
+		InitReply::Hit(value) => value,
+		InitReply::Done(value) => value,
+		InitReply::Lead => {
+			let guard = LeadGuard::new(sender.clone(), key.clone());
+			let value: Cacheable = Arc::new(init().await);
+			let command = CacheCommand::Complete(key, Arc::clone(&value), ttl_ms);
+			sender.send(command).map_err(|e| Error::ThreadErr(e.to_string()))?;
+			guard.disarm();
+			value
+		}
+	};
+
+	value.downcast().map_err(|_| Error::InvalidCast)
+}
+
 pub async fn remove(key: String) -> Result<()> {
-	let sender = get_sender();
+	let sender = get_sender(&key);
 	let command = CacheCommand::Remove(key.to_string());
 	sender.send(command).map_err(|e| Error::ThreadErr(e.to_string()))
 }
 
-fn get_sender() -> SyncSender<CacheCommand> {
+/// Subscribes to [`EvictionEvent`]s fired whenever an entry leaves the
+/// cache, whether through TTL expiry, capacity eviction, an overwriting
+/// `Put`, or an explicit `remove`. Since entries are spread across shards,
+/// this subscribes on every shard's thread and merges their events into one
+/// combined receiver.
+pub async fn subscribe() -> Result<tokio::sync::mpsc::Receiver<EvictionEvent>> {
+	let senders = all_senders();
+	let (merged_send, merged_recv) = tokio::sync::mpsc::channel(128);
+
+	for sender in senders {
+		let (send, mut recv) = tokio::sync::mpsc::channel(128);
+		let command = CacheCommand::Subscribe(send);
+		sender.send(command).map_err(|e| Error::ThreadErr(e.to_string()))?;
+
+		let merged_send = merged_send.clone();
+		tokio::spawn(async move {
+			while let Some(event) = recv.recv().await {
+				if merged_send.send(event).await.is_err() {
+					break;
+				}
+			}
+		});
+	}
+
+	Ok(merged_recv)
+}
+
+/// Picks the shard responsible for `key` (by hash) and returns a sender for
+/// its cache thread.
+fn get_sender(key: &str) -> SyncSender<CacheCommand> {
+	let read = CACHE_SEND.read().expect("[MEMCACHE] Cache send is poisoned");
+	let senders = read.as_ref().expect("[MEMCACHE] Not initialized");
+	let shard = shard_for(key, senders.len());
+	senders[shard].clone()
+}
+
+/// All shard senders, for operations (like `subscribe`) that must fan out
+/// to every shard rather than route to just one.
+fn all_senders() -> Vec<SyncSender<CacheCommand>> {
 	CACHE_SEND.read()
 		.expect("[MEMCACHE] Cache send is poisoned")
 		.as_ref()
@@ -90,6 +297,13 @@ fn get_sender() -> SyncSender<CacheCommand> {
 		.clone()
 }
 
+fn shard_for(key: &str, num_shards: usize) -> usize {
+	use std::hash::{Hash, Hasher};
+	let mut hasher = std::collections::hash_map::DefaultHasher::new();
+	key.hash(&mut hasher);
+	(hasher.finish() as usize) % num_shards
+}
+
 async fn ret_val<T: Any + Send + Sync>(mut recv: tokio::sync::mpsc::Receiver<Result<Cacheable>>) -> Result<Arc<T>> {
 	recv.recv().await
 		.ok_or(Error::ThreadDisconnected)??
@@ -97,10 +311,30 @@ async fn ret_val<T: Any + Send + Sync>(mut recv: tokio::sync::mpsc::Receiver<Res
 		.map_err(|_| Error::InvalidCast)
 }
 
-fn run(receiver: Receiver<CacheCommand>) {
+fn run(receiver: Receiver<CacheCommand>, max_entries: Option<usize>, admission: bool, max_weight: Option<u64>, expected_keys: Option<usize>) {
 	let mut cache: HashMap<String, CacheItem> = HashMap::new();
 	let mut min_expire = Instant::now();
 
+	// Recency tracker for LRU eviction: `order` maps sequence number -> key
+	// (smallest sequence is least-recently-used), `seqs` maps key -> its
+	// current sequence number so it can be relocated in `order` on access.
+	// Populated whenever either capacity bound is active.
+	let mut order: BTreeMap<u64, String> = BTreeMap::new();
+	let mut next_seq: u64 = 0;
+	let track_order = max_entries.is_some() || max_weight.is_some();
+	let mut total_weight: u64 = 0;
+
+	let mut sketch = match (max_entries, admission) {
+		(Some(capacity), true) => Some(CountMinSketch::new(capacity, expected_keys)),
+		_ => None,
+	};
+
+	// Keys currently being computed by a `get_or_init` leader, and the
+	// waiters parked behind them for the result.
+	let mut in_flight: HashMap<String, Vec<tokio::sync::mpsc::Sender<InitReply>>> = HashMap::new();
+
+	let mut listeners: Vec<tokio::sync::mpsc::Sender<EvictionEvent>> = Vec::new();
+
 	loop {
 		// Wait for a command
 		let command = match receiver.recv() {
@@ -124,41 +358,307 @@ fn run(receiver: Receiver<CacheCommand>) {
 
 				if item.expires <= now {
 					send.blocking_send(Err(Error::Expired)).unwrap();
-					cache.remove(&key);
+					if let Some(old) = cache.remove(&key) {
+						order.remove(&old.seq);
+						total_weight -= old.weight;
+					}
+					emit(&mut listeners, &key, EvictionCause::Expired);
 					continue;
 				}
 
 				let value = Arc::clone(&item.data);
 				send.blocking_send(Ok(value)).unwrap();
 
+				if let Some(sketch) = &mut sketch {
+					sketch.increment(&key);
+				}
+
+				if track_order {
+					next_seq = touch(&mut cache, &mut order, &key, next_seq);
+				}
+
 				if refresh {
 					let item = cache.get_mut(&key).unwrap();
 					item.expires = now + Duration::from_millis(item.ttl_ms);
 				}
 			},
-			CacheCommand::Put(key, value, ttl_ms) => {
-				let item = CacheItem {
-					data: value,
-					expires: now + Duration::from_millis(ttl_ms),
-					ttl_ms,
-				};
-
-				cache.insert(key, item);
+			CacheCommand::Put(key, value, ttl_ms, weight) => {
+				// Admission compares the candidate's estimate against the
+				// victim's, so it must run before this put's own increment
+				// inflates the candidate's measured frequency to >= 1.
+				insert_item(&mut cache, &mut order, &sketch, max_entries, max_weight, &mut total_weight, &mut next_seq, now, key.clone(), value, ttl_ms, weight, &mut listeners);
+
+				if let Some(sketch) = &mut sketch {
+					sketch.increment(&key);
+				}
 			},
 			CacheCommand::Remove(key) => {
-				cache.remove(&key);
+				if let Some(old) = cache.remove(&key) {
+					order.remove(&old.seq);
+					total_weight -= old.weight;
+					emit(&mut listeners, &key, EvictionCause::Removed);
+				}
+			},
+			CacheCommand::GetOrInit(key, send) => {
+				if let Some(item) = cache.get(&key) {
+					if item.expires > now {
+						let value = Arc::clone(&item.data);
+						send.blocking_send(InitReply::Hit(value)).ok();
+
+						if let Some(sketch) = &mut sketch {
+							sketch.increment(&key);
+						}
+
+						if track_order {
+							next_seq = touch(&mut cache, &mut order, &key, next_seq);
+						}
+
+						continue;
+					}
+
+					if let Some(old) = cache.remove(&key) {
+						order.remove(&old.seq);
+						total_weight -= old.weight;
+						emit(&mut listeners, &key, EvictionCause::Expired);
+					}
+				}
+
+				match in_flight.get_mut(&key) {
+					Some(waiters) => waiters.push(send),
+					None => {
+						in_flight.insert(key.clone(), Vec::new());
+						send.blocking_send(InitReply::Lead).ok();
+					}
+				}
+			},
+			CacheCommand::Complete(key, value, ttl_ms) => {
+				// Same ordering as `Put`: judge admission before this
+				// completion's own increment inflates the candidate's count.
+				insert_item(&mut cache, &mut order, &sketch, max_entries, max_weight, &mut total_weight, &mut next_seq, now, key.clone(), Arc::clone(&value), ttl_ms, 1, &mut listeners);
+
+				if let Some(sketch) = &mut sketch {
+					sketch.increment(&key);
+				}
+
+				if let Some(waiters) = in_flight.remove(&key) {
+					for waiter in waiters {
+						waiter.blocking_send(InitReply::Done(Arc::clone(&value))).ok();
+					}
+				}
+			},
+			CacheCommand::AbandonLead(key) => {
+				// The leader's future was dropped (e.g. a `timeout` or
+				// `select!`) before it could report back via `Complete`.
+				// Promote the oldest live waiter to take over as leader so
+				// the key doesn't stay stuck in `in_flight` forever; a
+				// waiter can itself have been cancelled in the meantime, so
+				// keep trying until one accepts or none are left.
+				if let Some(mut waiters) = in_flight.remove(&key) {
+					while !waiters.is_empty() {
+						let new_leader = waiters.remove(0);
+						if new_leader.blocking_send(InitReply::Lead).is_ok() {
+							in_flight.insert(key, waiters);
+							break;
+						}
+					}
+				}
+			},
+			CacheCommand::Subscribe(send) => {
+				listeners.push(send);
 			}
 		}
 
 		if now > min_expire {
-			if let Some(v) = cleanup(&mut cache) {
+			if let Some(v) = cleanup(&mut cache, &mut order, &mut total_weight, &mut listeners) {
 				min_expire = v;
 			}
 		}
 	}
 }
 
-fn cleanup(cache: &mut HashMap<String, CacheItem>) -> Option<Instant> {
+/// Fires an [`EvictionEvent`] for `key`/`cause` to every live listener,
+/// dropping any whose receiver has gone away.
+fn emit(listeners: &mut Vec<tokio::sync::mpsc::Sender<EvictionEvent>>, key: &str, cause: EvictionCause) {
+	if listeners.is_empty() {
+		return;
+	}
+
+	let event = EvictionEvent { key: key.to_string(), cause };
+	listeners.retain(|l| l.try_send(event.clone()).is_ok());
+}
+
+/// Inserts `value` (of the given `weight`) under `key`, first evicting LRU
+/// victims to stay within `max_entries` and/or `max_weight`. Count-based
+/// eviction is gated by `sketch` when present (the newcomer must be
+/// estimated at least as popular as the victim, else the insert is
+/// dropped); weight-based eviction is unconditional LRU since the sketch
+/// has no notion of the newcomer's weight. Shared by `CacheCommand::Put`
+/// and `Complete`.
+///
+/// Callers must increment `sketch` for `key` *after* this returns, not
+/// before: the admission check reads the candidate's current estimate, and
+/// incrementing first would inflate every newcomer to look at least as
+/// popular as the victim it's being compared against.
+#[allow(clippy::too_many_arguments)]
+fn insert_item(
+	cache: &mut HashMap<String, CacheItem>,
+	order: &mut BTreeMap<u64, String>,
+	sketch: &Option<CountMinSketch>,
+	max_entries: Option<usize>,
+	max_weight: Option<u64>,
+	total_weight: &mut u64,
+	next_seq: &mut u64,
+	now: Instant,
+	key: String,
+	value: Cacheable,
+	ttl_ms: u64,
+	weight: u64,
+	listeners: &mut Vec<tokio::sync::mpsc::Sender<EvictionEvent>>,
+) {
+	if let Some(max_entries) = max_entries {
+		if !cache.contains_key(&key) && cache.len() >= max_entries {
+			if let Some((&seq, victim)) = order.iter().next() {
+				if let Some(sketch) = sketch {
+					if sketch.estimate(&key) < sketch.estimate(victim) {
+						return;
+					}
+				}
+
+				let victim = victim.clone();
+				order.remove(&seq);
+				if let Some(old) = cache.remove(&victim) {
+					*total_weight -= old.weight;
+				}
+				emit(listeners, &victim, EvictionCause::CapacityEvicted);
+			}
+		}
+	}
+
+	if let Some(max_weight) = max_weight {
+		let existing_weight = cache.get(&key).map(|i| i.weight).unwrap_or(0);
+		while *total_weight + weight - existing_weight > max_weight {
+			let Some((&seq, victim)) = order.iter().next() else { break; };
+			if *victim == key {
+				break;
+			}
+
+			let victim = victim.clone();
+			order.remove(&seq);
+			if let Some(old) = cache.remove(&victim) {
+				*total_weight -= old.weight;
+			}
+			emit(listeners, &victim, EvictionCause::CapacityEvicted);
+		}
+	}
+
+	let seq = *next_seq;
+	*next_seq += 1;
+
+	let item = CacheItem {
+		data: value,
+		expires: now + Duration::from_millis(ttl_ms),
+		ttl_ms,
+		seq,
+		weight,
+	};
+
+	if let Some(old) = cache.insert(key.clone(), item) {
+		order.remove(&old.seq);
+		*total_weight -= old.weight;
+		emit(listeners, &key, EvictionCause::Replaced);
+	}
+
+	*total_weight += weight;
+
+	if max_entries.is_some() || max_weight.is_some() {
+		order.insert(seq, key);
+	}
+}
+
+/// Bumps `key`'s position to the back of the LRU order by assigning it a
+/// fresh sequence number, returning the next sequence number to use.
+fn touch(cache: &mut HashMap<String, CacheItem>, order: &mut BTreeMap<u64, String>, key: &str, next_seq: u64) -> u64 {
+	let Some(item) = cache.get_mut(key) else { return next_seq; };
+
+	order.remove(&item.seq);
+	item.seq = next_seq;
+	order.insert(next_seq, key.to_string());
+
+	next_seq + 1
+}
+
+const CMS_ROWS: usize = 4;
+const CMS_MAX_COUNT: u8 = 15;
+
+/// A Count-Min Sketch of 4-bit (saturating at 15) counters, used to estimate
+/// per-key access frequency for TinyLFU admission. Counters are periodically
+/// halved ("aged") so the sketch tracks recent popularity rather than
+/// all-time totals.
+struct CountMinSketch {
+	width: usize,
+	rows: [Vec<u8>; CMS_ROWS],
+	increments: u64,
+	age_every: u64,
+}
+
+impl CountMinSketch {
+	/// `expected_keys` is the approximate cardinality of the working set the
+	/// sketch needs to discriminate between, which for scan-heavy workloads
+	/// (the whole reason admission exists) can be orders of magnitude larger
+	/// than `capacity`. Sizing width off `capacity` alone means a large cold
+	/// scan saturates the same rows the hot keys live in, destroying the
+	/// signal admission is supposed to provide.
+	fn new(capacity: usize, expected_keys: Option<usize>) -> Self {
+		let width = expected_keys.unwrap_or(capacity.saturating_mul(8)).max(16);
+		Self {
+			width,
+			rows: std::array::from_fn(|_| vec![0u8; width]),
+			increments: 0,
+			age_every: (width as u64) * 8,
+		}
+	}
+
+	fn increment(&mut self, key: &str) {
+		for (row_idx, row) in self.rows.iter_mut().enumerate() {
+			let slot = &mut row[Self::slot(key, row_idx, self.width)];
+			if *slot < CMS_MAX_COUNT {
+				*slot += 1;
+			}
+		}
+
+		self.increments += 1;
+		if self.increments >= self.age_every {
+			self.age();
+		}
+	}
+
+	fn estimate(&self, key: &str) -> u8 {
+		self.rows.iter().enumerate()
+			.map(|(row_idx, row)| row[Self::slot(key, row_idx, self.width)])
+			.min()
+			.unwrap_or(0)
+	}
+
+	fn age(&mut self) {
+		for row in self.rows.iter_mut() {
+			for count in row.iter_mut() {
+				*count /= 2;
+			}
+		}
+
+		self.increments = 0;
+	}
+
+	fn slot(key: &str, row: usize, width: usize) -> usize {
+		use std::hash::{Hash, Hasher};
+		let mut hasher = std::collections::hash_map::DefaultHasher::new();
+		row.hash(&mut hasher);
+		key.hash(&mut hasher);
+		(hasher.finish() as usize) % width
+	}
+}
+
+fn cleanup(cache: &mut HashMap<String, CacheItem>, order: &mut BTreeMap<u64, String>, total_weight: &mut u64, listeners: &mut Vec<tokio::sync::mpsc::Sender<EvictionEvent>>) -> Option<Instant> {
 	let mut expired = Vec::new();
 	let mut min_expire = None;
 	let now = Instant::now();
@@ -172,7 +672,11 @@ fn cleanup(cache: &mut HashMap<String, CacheItem>) -> Option<Instant> {
 
 	let clean_count = expired.len();
 	for key in expired {
-		cache.remove(&key);
+		if let Some(item) = cache.remove(&key) {
+			order.remove(&item.seq);
+			*total_weight -= item.weight;
+			emit(listeners, &key, EvictionCause::Expired);
+		}
 	}
 
 	println!("[MEMCACHE] GC collected {clean_count} items.");
@@ -0,0 +1,107 @@
+use std::sync::{Arc, RwLock};
+use serde::{Serialize, de::DeserializeOwned};
+use redis::Commands;
+
+use crate::{Error, Result};
+
+/// Values storable in the Redis-backed L2 tier, in addition to the
+/// existing, purely local `Any`-based API.
+pub trait Serializable: Serialize + DeserializeOwned + Send + Sync + 'static {}
+impl<T: Serialize + DeserializeOwned + Send + Sync + 'static> Serializable for T {}
+
+static REDIS_CLIENT: RwLock<Option<redis::Client>> = RwLock::new(None);
+
+/// Like [`crate::init_bounded`], but additionally wires up a Redis instance
+/// as a shared/persistent L2 tier for [`put_persistent`]/[`get_persistent`].
+/// A local L1 miss transparently falls through to Redis and promotes the
+/// value back into L1; `put_persistent` writes through to Redis with the
+/// same TTL. The plain `Any`-based API (`get`/`put`/...) is untouched and
+/// keeps working purely against L1.
+pub async fn init_persistent(command_buffer_size: Option<usize>, max_entries: Option<usize>, admission: bool, max_weight: Option<u64>, num_shards: usize, expected_keys: Option<usize>, redis_url: &str) -> Result<()> {
+	crate::init_bounded(command_buffer_size, max_entries, admission, max_weight, num_shards, expected_keys).await?;
+
+	let client = redis::Client::open(redis_url).map_err(|e| Error::ThreadErr(e.to_string()))?;
+	let mut write = REDIS_CLIENT.write().expect("[MEMCACHE] Redis client is poisoned");
+	*write = Some(client);
+
+	Ok(())
+}
+
+/// Writes `value` through to both the local L1 cache and the Redis L2 tier.
+/// L1 gets `ttl_ms` as is; Redis takes whole seconds, so its expiry is
+/// `ttl_ms` rounded down to seconds, and at least 1 second.
+pub async fn put_persistent<T: Serializable>(key: String, value: T, ttl_ms: u64) -> Result<()> {
+	let bytes = bincode::serialize(&value).map_err(|e| Error::ThreadErr(e.to_string()))?;
+
+	let redis_key = key.clone();
+	tokio::task::spawn_blocking(move || -> Result<()> {
+		let mut conn = redis_connection()?;
+		conn.set_ex::<_, _, ()>(redis_key, bytes, (ttl_ms / 1000).max(1))
+			.map_err(|e| Error::ThreadErr(e.to_string()))
+	}).await.map_err(|e| Error::ThreadErr(e.to_string()))??;
+
+	crate::put(key, value, ttl_ms).await
+}
+
+/// Gets `key`, transparently falling through to the Redis L2 tier (and
+/// promoting the result back into L1) on a local miss or expiry.
+pub async fn get_persistent<T: Serializable>(key: String) -> Result<Arc<T>> {
+	match crate::get::<T>(key.clone()).await {
+		Ok(value) => return Ok(value),
+		Err(Error::NoValue) | Err(Error::Expired) => {},
+		Err(e) => return Err(e),
+	}
+
+	let redis_key = key.clone();
+	let fetched = tokio::task::spawn_blocking(move || -> Result<Option<(Vec<u8>, i64)>> {
+		let mut conn = redis_connection()?;
+		let bytes: Option<Vec<u8>> = conn.get(&redis_key).map_err(|e| Error::ThreadErr(e.to_string()))?;
+		let Some(bytes) = bytes else { return Ok(None); };
+
+		let ttl_secs: i64 = conn.ttl(&redis_key).map_err(|e| Error::ThreadErr(e.to_string()))?;
+		Ok(Some((bytes, ttl_secs)))
+	}).await.map_err(|e| Error::ThreadErr(e.to_string()))??;
+
+	let Some((bytes, ttl_secs)) = fetched else { return Err(Error::NoValue); };
+
+	let value: Arc<T> = Arc::new(bincode::deserialize(&bytes).map_err(|e| Error::ThreadErr(e.to_string()))?);
+	// Redis reports no TTL as -1 and a missing key as -2; neither should
+	// happen here since `put_persistent` always writes with an expiry, but
+	// fall back to a conservative default rather than caching forever.
+	let ttl_ms = if ttl_secs > 0 { ttl_secs as u64 * 1000 } else { 60_000 };
+
+	crate::put_arc(key, Arc::clone(&value), ttl_ms, 1).await?;
+	Ok(value)
+}
+
+fn redis_connection() -> Result<redis::Connection> {
+	REDIS_CLIENT.read()
+		.expect("[MEMCACHE] Redis client is poisoned")
+		.as_ref()
+		.expect("[MEMCACHE] Not initialized with init_persistent")
+		.get_connection()
+		.map_err(|e| Error::ThreadErr(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use serde::{Serialize, Deserialize};
+
+	#[derive(Debug, PartialEq, Serialize, Deserialize)]
+	struct Sample {
+		id: u32,
+		name: String,
+	}
+
+	// `put_persistent`/`get_persistent` need a reachable Redis (see
+	// `tests/persistent.rs`), but the bincode encode/decode they rely on is
+	// pure and worth covering without one.
+	#[test]
+	fn test_bincode_round_trip() {
+		let value = Sample { id: 7, name: String::from("widget") };
+		let bytes = bincode::serialize(&value).unwrap();
+		let decoded: Sample = bincode::deserialize(&bytes).unwrap();
+
+		assert_eq!(value, decoded);
+	}
+}
@@ -0,0 +1,24 @@
+// `init_bounded` sets up its own process-global cache, so this lives in its
+// own integration test binary rather than alongside `src/tests.rs`.
+
+#[tokio::test]
+async fn test_admission_protects_hot_key() {
+	memcache::init_bounded(None, Some(1), true, None, 1, None).await.unwrap();
+
+	memcache::put(String::from("hot"), 1, 60_000).await.unwrap();
+
+	// Access "hot" repeatedly so the sketch rates it far above a
+	// one-off newcomer.
+	for _ in 0..20 {
+		memcache::get::<i32>(String::from("hot")).await.unwrap();
+	}
+
+	// A single cold put should be rejected rather than evicting "hot".
+	memcache::put(String::from("cold"), 2, 60_000).await.unwrap();
+
+	let val = memcache::get::<i32>(String::from("hot")).await.unwrap();
+	assert_eq!(*val, 1);
+
+	let err = memcache::get::<i32>(String::from("cold")).await.unwrap_err();
+	assert_eq!(err, memcache::Error::NoValue);
+}
@@ -0,0 +1,33 @@
+// `init_bounded` sets up its own process-global cache, so this lives in its
+// own integration test binary rather than alongside `src/tests.rs`.
+
+#[tokio::test]
+async fn test_admission_survives_large_cold_scan() {
+	memcache::init_bounded(None, Some(10), true, None, 1, Some(4096)).await.unwrap();
+
+	for i in 0..10 {
+		memcache::put(format!("hot{i}"), i, 60_000).await.unwrap();
+	}
+
+	for _ in 0..30 {
+		for i in 0..10 {
+			memcache::get::<i32>(format!("hot{i}")).await.unwrap();
+		}
+	}
+
+	// A single-pass scan of many distinct cold keys should not be able to
+	// evict every hot key, the way pure LRU (or a too-small/self-inflating
+	// sketch) would.
+	for i in 0..2000 {
+		memcache::put(format!("cold{i}"), i, 60_000).await.unwrap();
+	}
+
+	let mut survivors = 0;
+	for i in 0..10 {
+		if memcache::get::<i32>(format!("hot{i}")).await.is_ok() {
+			survivors += 1;
+		}
+	}
+
+	assert!(survivors > 0, "expected at least one hot key to survive the cold scan, got {survivors}/10");
+}
@@ -0,0 +1,25 @@
+// `init_bounded` sets up its own process-global cache, so this lives in its
+// own integration test binary rather than alongside `src/tests.rs`.
+
+#[tokio::test]
+async fn test_bounded_lru_eviction() {
+	memcache::init_bounded(None, Some(2), false, None, 1, None).await.unwrap();
+
+	memcache::put(String::from("a"), 1, 60_000).await.unwrap();
+	memcache::put(String::from("b"), 2, 60_000).await.unwrap();
+
+	// Touch "a" so "b" becomes the least-recently-used entry.
+	memcache::get::<i32>(String::from("a")).await.unwrap();
+
+	// Inserting a third key should evict "b", not "a".
+	memcache::put(String::from("c"), 3, 60_000).await.unwrap();
+
+	let val = memcache::get::<i32>(String::from("a")).await.unwrap();
+	assert_eq!(*val, 1);
+
+	let val = memcache::get::<i32>(String::from("c")).await.unwrap();
+	assert_eq!(*val, 3);
+
+	let err = memcache::get::<i32>(String::from("b")).await.unwrap_err();
+	assert_eq!(err, memcache::Error::NoValue);
+}
@@ -0,0 +1,33 @@
+// `init_bounded` sets up its own process-global cache, so this lives in its
+// own integration test binary rather than alongside `src/tests.rs`.
+
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_bounded_expiry_does_not_leak_past_capacity() {
+	memcache::init_bounded(None, Some(2), false, None, 1, None).await.unwrap();
+
+	memcache::put(String::from("a"), 1, 20).await.unwrap();
+	memcache::put(String::from("b"), 2, 60_000).await.unwrap();
+
+	tokio::time::sleep(Duration::from_millis(50)).await;
+
+	// Surface the expiry via a direct `get`, which removes "a" from the
+	// cache (but, before this fix, left its LRU order entry behind).
+	let err = memcache::get::<i32>(String::from("a")).await.unwrap_err();
+	assert_eq!(err, memcache::Error::Expired);
+
+	// "c" takes the slot "a" left; "d" must then evict a live entry rather
+	// than a stale order entry, since the cache is capped at 2.
+	memcache::put(String::from("c"), 3, 60_000).await.unwrap();
+	memcache::put(String::from("d"), 4, 60_000).await.unwrap();
+
+	let mut live = 0;
+	for key in ["b", "c", "d"] {
+		if memcache::get::<i32>(String::from(key)).await.is_ok() {
+			live += 1;
+		}
+	}
+
+	assert!(live <= 2, "expected at most 2 live entries in a cache capped at max_entries=2, got {live}");
+}
@@ -0,0 +1,35 @@
+// `init` sets up its own process-global cache, so this lives in its own
+// integration test binary rather than alongside `src/tests.rs`.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[tokio::test]
+async fn test_get_or_init_dedupes_concurrent_callers() {
+	memcache::init(None).await.unwrap();
+
+	let mut handles = Vec::new();
+	for _ in 0..10 {
+		handles.push(tokio::spawn(async {
+			memcache::get_or_init(String::from("shared"), 60_000, || async {
+				CALLS.fetch_add(1, Ordering::SeqCst);
+				tokio::time::sleep(Duration::from_millis(50)).await;
+				42
+			}).await.unwrap()
+		}));
+	}
+
+	let mut results = Vec::new();
+	for handle in handles {
+		results.push(handle.await.unwrap());
+	}
+
+	assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+	for value in &results {
+		assert_eq!(**value, 42);
+		assert!(Arc::ptr_eq(value, &results[0]));
+	}
+}
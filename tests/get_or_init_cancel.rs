@@ -0,0 +1,30 @@
+// `init` sets up its own process-global cache, so this lives in its own
+// integration test binary rather than alongside `src/tests.rs`.
+
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_get_or_init_recovers_from_cancelled_leader() {
+	memcache::init(None).await.unwrap();
+
+	// The leader is cancelled mid-init; its `init` never gets to report back
+	// via `Complete`.
+	let leader = tokio::time::timeout(
+		Duration::from_millis(20),
+		memcache::get_or_init(String::from("cancelled"), 60_000, || async {
+			tokio::time::sleep(Duration::from_millis(200)).await;
+			42
+		}),
+	).await;
+	assert!(leader.is_err(), "expected the leader to be cancelled by the timeout");
+
+	// A second, independent call on the same key must not be stuck waiting
+	// on a leader that will never report back.
+	let second = tokio::time::timeout(
+		Duration::from_millis(500),
+		memcache::get_or_init(String::from("cancelled"), 60_000, || async { 7 }),
+	).await;
+
+	let value = second.expect("get_or_init should not hang after its leader was cancelled").unwrap();
+	assert_eq!(*value, 7);
+}
@@ -0,0 +1,28 @@
+// `init_persistent` sets up its own process-global cache (on top of
+// `init_bounded`'s process-global `CACHE_SEND`), so this lives in its own
+// integration test binary rather than alongside `src/tests.rs`.
+//
+// Ignored by default since it needs a reachable Redis; run with
+// `cargo test --test persistent -- --ignored` against a local instance
+// (e.g. `redis-server` listening on the default port).
+
+#[tokio::test]
+#[ignore]
+async fn test_persistent_round_trips_through_redis() {
+	memcache::init_persistent(None, None, false, None, 1, None, "redis://127.0.0.1/")
+		.await
+		.unwrap();
+
+	memcache::put_persistent(String::from("answer"), 42, 60_000).await.unwrap();
+
+	// A fresh local miss (simulated by removing the L1 entry) should fall
+	// through to Redis and come back with the same value.
+	memcache::remove(String::from("answer")).await.unwrap();
+
+	let val = memcache::get_persistent::<i32>(String::from("answer")).await.unwrap();
+	assert_eq!(*val, 42);
+
+	// And it should now be promoted back into L1.
+	let val = memcache::get::<i32>(String::from("answer")).await.unwrap();
+	assert_eq!(*val, 42);
+}
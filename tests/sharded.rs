@@ -0,0 +1,20 @@
+// `init_bounded` sets up its own process-global cache, so this lives in its
+// own integration test binary rather than alongside `src/tests.rs`.
+
+#[tokio::test]
+async fn test_sharded_routes_keys_across_threads() {
+	memcache::init_bounded(None, None, false, None, 4, None).await.unwrap();
+
+	for i in 0..50 {
+		memcache::put(format!("key{i}"), i, 60_000).await.unwrap();
+	}
+
+	for i in 0..50 {
+		let val = memcache::get::<i32>(format!("key{i}")).await.unwrap();
+		assert_eq!(*val, i);
+	}
+
+	memcache::remove(String::from("key0")).await.unwrap();
+	let err = memcache::get::<i32>(String::from("key0")).await.unwrap_err();
+	assert_eq!(err, memcache::Error::NoValue);
+}
@@ -0,0 +1,31 @@
+// `init_bounded` sets up its own process-global cache, so this lives in its
+// own integration test binary rather than alongside `src/tests.rs`.
+
+#[tokio::test]
+async fn test_sharded_subscribe_receives_events_from_every_shard() {
+	memcache::init_bounded(None, None, false, None, 4, None).await.unwrap();
+
+	let mut events = memcache::subscribe().await.unwrap();
+
+	// Spread enough keys across shards that several land on different ones,
+	// then remove them all; `subscribe` should see every removal regardless
+	// of which shard's thread fired it.
+	let keys: Vec<String> = (0..20).map(|i| format!("sharded{i}")).collect();
+	for key in &keys {
+		memcache::put(key.clone(), 1, 60_000).await.unwrap();
+	}
+	for key in &keys {
+		memcache::remove(key.clone()).await.unwrap();
+	}
+
+	let mut seen = std::collections::HashSet::new();
+	while seen.len() < keys.len() {
+		let event = events.recv().await.unwrap();
+		assert_eq!(event.cause, memcache::EvictionCause::Removed);
+		seen.insert(event.key);
+	}
+
+	for key in &keys {
+		assert!(seen.contains(key));
+	}
+}
@@ -0,0 +1,27 @@
+// `init` sets up its own process-global cache, so this lives in its own
+// integration test binary rather than alongside `src/tests.rs`.
+
+use std::time::Duration;
+use memcache::EvictionCause;
+
+#[tokio::test]
+async fn test_subscribe_reports_removed_and_replaced() {
+	memcache::init(None).await.unwrap();
+	let mut events = memcache::subscribe().await.unwrap();
+
+	memcache::put(String::from("k"), 1, 60_000).await.unwrap();
+	memcache::put(String::from("k"), 2, 60_000).await.unwrap();
+	memcache::remove(String::from("k")).await.unwrap();
+
+	let replaced = events.recv().await.unwrap();
+	assert_eq!(replaced.key, "k");
+	assert_eq!(replaced.cause, EvictionCause::Replaced);
+
+	let removed = events.recv().await.unwrap();
+	assert_eq!(removed.key, "k");
+	assert_eq!(removed.cause, EvictionCause::Removed);
+
+	// Nothing else should be pending.
+	let timed_out = tokio::time::timeout(Duration::from_millis(50), events.recv()).await;
+	assert!(timed_out.is_err());
+}
@@ -0,0 +1,26 @@
+// `init_bounded` sets up its own process-global cache, so this lives in its
+// own integration test binary rather than alongside `src/tests.rs`.
+
+#[tokio::test]
+async fn test_weighted_eviction_respects_budget() {
+	memcache::init_bounded(None, None, false, Some(10), 1, None).await.unwrap();
+
+	// A heavy "buffer" entry takes most of the budget.
+	memcache::put_weighted(String::from("buffer"), vec![0u8; 1], 60_000, 8).await.unwrap();
+
+	// A light entry fits alongside it.
+	memcache::put(String::from("a"), 1, 60_000).await.unwrap();
+
+	// Touch "buffer" so "a" becomes the least-recently-used entry.
+	memcache::get::<Vec<u8>>(String::from("buffer")).await.unwrap();
+
+	// A new heavy entry evicts in LRU order until it fits: "a" first, then
+	// "buffer" too, since 8 + 8 is still over the budget of 10.
+	memcache::put_weighted(String::from("other"), vec![0u8; 1], 60_000, 8).await.unwrap();
+
+	let err = memcache::get::<i32>(String::from("a")).await.unwrap_err();
+	assert_eq!(err, memcache::Error::NoValue);
+
+	let val = memcache::get::<Vec<u8>>(String::from("other")).await.unwrap();
+	assert_eq!(val.len(), 1);
+}